@@ -0,0 +1,130 @@
+use ast::NodeKind;
+
+/// A single step of the flat event stream a `Parser` can emit instead of
+/// (or alongside) directly allocating the typed AST, following
+/// rust-analyzer's `event.rs`. Currently only block boundaries (braces)
+/// and the statement/block node shape around them are recorded — see the
+/// `events` field doc on `Parser` for exactly what's covered and what
+/// isn't yet. Once every significant token and all trivia is recorded,
+/// replaying a `Vec<Event>` would be enough to rebuild either the
+/// existing arena AST or a lossless concrete tree without re-lexing the
+/// source; that point hasn't been reached yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Opens a new node of the given kind; closed by the next matching
+    /// `FinishNode`.
+    StartNode(NodeKind),
+
+    /// A single token, identified by its byte range in the source text.
+    Token { start: u32, end: u32 },
+
+    /// Closes the node most recently opened by `StartNode`.
+    FinishNode,
+
+    /// A parse error was recorded; recovery (if any) happens around it,
+    /// it does not itself close or open a node.
+    Error,
+}
+
+/// A piece of trivia (whitespace or a comment) discarded by the `Lexer`
+/// between two significant tokens, recorded so it can be reattached to
+/// the surrounding nodes and the source reconstructed byte-for-byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trivia {
+    pub start: u32,
+    pub end: u32,
+    pub kind: TriviaKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    LineComment,
+    BlockComment,
+}
+
+/// The flat output of event-stream parsing: the `Event`s themselves, plus
+/// the trivia that fell between tokens, kept separate so consumers that
+/// don't care about formatting (the typed-AST builder) can ignore it
+/// entirely.
+#[derive(Debug, Default)]
+pub struct EventBuffer {
+    events: Vec<Event>,
+    trivia: Vec<Trivia>,
+}
+
+impl EventBuffer {
+    pub fn new() -> Self {
+        EventBuffer {
+            events: Vec::new(),
+            trivia: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn start_node(&mut self, kind: NodeKind) {
+        self.events.push(Event::StartNode(kind));
+    }
+
+    #[inline]
+    pub fn token(&mut self, start: u32, end: u32) {
+        self.events.push(Event::Token { start, end });
+    }
+
+    #[inline]
+    pub fn finish_node(&mut self) {
+        self.events.push(Event::FinishNode);
+    }
+
+    #[inline]
+    pub fn error(&mut self) {
+        self.events.push(Event::Error);
+    }
+
+    #[inline]
+    pub fn trivia(&mut self, start: u32, end: u32, kind: TriviaKind) {
+        self.trivia.push(Trivia { start, end, kind });
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn trivia_pieces(&self) -> &[Trivia] {
+        &self.trivia
+    }
+
+    /// Reconstructs source text from the events and trivia collected so
+    /// far, by slicing `source` at each token/trivia range in order.
+    ///
+    /// This is NOT currently a byte-for-byte round trip, for two
+    /// independent reasons (see the `events` field doc on `Parser`):
+    /// `trivia()` is never called, so whitespace/comments are always
+    /// dropped; and `record_token` is only ever called for a block's
+    /// opening/closing brace, not for the tokens statements and
+    /// expressions are made of (keywords, identifiers, operators,
+    /// semicolons, ...), so those are dropped too. `reconstruct` only
+    /// round-trips programs made of nothing but braces with no whitespace
+    /// between them (`{}`, `{{}}`); even an empty statement's `;` is
+    /// dropped, so `{;}` comes back as `{}`. The `event_mode_*` tests in
+    /// `parser::mod` check the event stream, not `reconstruct`.
+    pub fn reconstruct<'a>(&self, source: &'a str) -> String {
+        let mut ranges: Vec<(u32, u32)> = self.trivia.iter()
+            .map(|t| (t.start, t.end))
+            .chain(self.events.iter().filter_map(|event| match *event {
+                Event::Token { start, end } => Some((start, end)),
+                _ => None,
+            }))
+            .collect();
+
+        ranges.sort_by_key(|&(start, _)| start);
+
+        let mut out = String::with_capacity(source.len());
+
+        for (start, end) in ranges {
+            out.push_str(&source[start as usize..end as usize]);
+        }
+
+        out
+    }
+}
@@ -5,6 +5,9 @@ mod expression;
 mod statement;
 mod function;
 mod nested;
+mod recovery;
+mod pattern;
+mod event;
 
 use error::Error;
 use arena::Arena;
@@ -12,8 +15,10 @@ use module::Module;
 
 use self::error::ToError;
 use self::nested::*;
+use self::recovery::{TokenSet, STATEMENT_RECOVERY_SET, TOP_LEVEL_BOUNDARY, BLOCK_BOUNDARY};
+pub use self::event::{Event, EventBuffer, Trivia, TriviaKind};
 
-use ast::{Loc, Ptr, Statement, List, ListBuilder, EmptyListBuilder};
+use ast::{Loc, Ptr, Statement, StatementPtr, List, ListBuilder, EmptyListBuilder, NodeKind};
 use ast::{Parameter, ParameterKey, ParameterPtr, ParameterList, OperatorKind};
 use ast::{Expression, ExpressionPtr, ExpressionList, Block, BlockPtr};
 use ast::expression::BinaryExpression;
@@ -37,6 +42,37 @@ pub struct Parser<'ast> {
 
     /// AST under construction
     body: List<'ast, Loc<Statement<'ast>>>,
+
+    /// When present, parsing also records a flat `Event` stream alongside
+    /// building the typed AST: every top-level statement, every statement
+    /// directly inside a block, and every block is bracketed in
+    /// `StartNode`/`FinishNode`, and errors are marked inline. `None` by
+    /// default: collecting events has a cost that most callers (who only
+    /// want the typed AST) shouldn't pay.
+    ///
+    /// Token recording is INCOMPLETE: `record_token` is only ever called
+    /// for a block's opening/closing brace (in `block`/`unchecked_block`,
+    /// below). The actual tokens a statement or expression is made of —
+    /// keywords, identifiers, literals, operators, semicolons — are
+    /// produced inside `statement.rs`/`expression.rs`/`function.rs`,
+    /// which this checkout doesn't carry the source for and which this
+    /// change doesn't touch; so, for example, parsing `{;}` in event mode
+    /// records a `Token` for each brace but none for the `;` in between
+    /// (see `event_mode_records_nested_block_statements`, below). Until
+    /// those modules also call `record_token`, this event stream is a
+    /// structural (node-boundary) trace, not a token-complete one, and
+    /// isn't sufficient on its own to replay into a full AST or source
+    /// reconstruction.
+    ///
+    /// `EventBuffer` also has a `Trivia`/`trivia()` side channel for
+    /// whitespace and comments, which `reconstruct` needs for a true
+    /// byte-for-byte round trip. Nothing currently calls `trivia()`: the
+    /// `Lexer` in this tree discards trivia before the parser ever sees a
+    /// token, so recording it here would require threading it through
+    /// `Lexer` first. Until both that and the token gap above are closed,
+    /// `reconstruct`'s output should be treated as a best-effort partial
+    /// trace, not a lossless one.
+    events: Option<EventBuffer>,
 }
 
 impl<'ast> Parser<'ast> {
@@ -46,9 +82,26 @@ impl<'ast> Parser<'ast> {
             lexer: Lexer::new(arena, source),
             errors: Vec::new(),
             body: List::empty(),
+            events: None,
+        }
+    }
+
+    /// Like `new`, but also collects an `Event` stream while parsing,
+    /// retrievable afterwards with `take_events`. No trivia is collected:
+    /// the buffer's trivia list stays empty.
+    pub fn new_with_events(source: &str, arena: &'ast Arena) -> Self {
+        Parser {
+            events: Some(EventBuffer::new()),
+            .. Self::new(source, arena)
         }
     }
 
+    /// Consumes the `Parser`, returning the collected `EventBuffer` if
+    /// event collection was enabled via `new_with_events`.
+    pub fn take_events(self) -> Option<EventBuffer> {
+        self.events
+    }
+
     fn error<T: ToError>(&mut self) -> T {
         let err = self.lexer.invalid_token();
 
@@ -57,6 +110,31 @@ impl<'ast> Parser<'ast> {
         T::to_error()
     }
 
+    /// After an error has already been recorded, discard tokens until the
+    /// lexer lands on one of `set`'s resynchronization points (without
+    /// consuming it), so the caller can resume parsing from a clean
+    /// statement boundary instead of cascading into further spurious
+    /// errors on the same malformed input.
+    fn recover(&mut self, set: TokenSet) {
+        while !set.contains(self.lexer.token) && self.lexer.token != EndOfProgram {
+            self.lexer.consume();
+        }
+    }
+
+    /// Safety valve for `recover`: if parsing a statement produced an
+    /// error but left the lexer sitting on the exact token it started on
+    /// (e.g. a stray `}` that's already a member of the recovery set, so
+    /// `recover` has nothing to skip), force one token of progress so the
+    /// caller's loop can't spin forever re-parsing the same token. Never
+    /// forces past any of `boundary` (the caller's own natural stop
+    /// tokens, which it still needs to see unconsumed).
+    #[inline]
+    fn ensure_recovery_progress(&mut self, before: (u32, u32), boundary: TokenSet) {
+        if self.loc() == before && !boundary.contains(self.lexer.token) {
+            self.lexer.consume();
+        }
+    }
+
     #[inline]
     fn asi(&mut self) -> Asi {
         self.lexer.asi()
@@ -104,16 +182,73 @@ impl<'ast> Parser<'ast> {
             return;
         }
 
-        let statement = self.statement();
+        let statement = self.top_level_statement();
         let mut builder = ListBuilder::new(self.arena, statement);
 
         while self.lexer.token != EndOfProgram {
-            builder.push(self.statement());
+            builder.push(self.top_level_statement());
         }
 
         self.body = builder.into_list()
     }
 
+    /// Parses one top-level statement, wrapped in a `StartNode`/`FinishNode`
+    /// event pair (when event collection is enabled) and the same
+    /// error-recovery bookkeeping `raw_block` uses for nested statements.
+    #[inline]
+    fn top_level_statement(&mut self) -> StatementPtr<'ast> {
+        let before = self.loc();
+        let errors_before = self.errors.len();
+
+        self.record_start_node(NodeKind::Statement);
+        let statement = self.statement();
+        self.record_finish_node();
+
+        if self.errors.len() > errors_before {
+            self.record_error_event();
+            self.recover(STATEMENT_RECOVERY_SET);
+            self.ensure_recovery_progress(before, TOP_LEVEL_BOUNDARY);
+        }
+
+        statement
+    }
+
+    /// Records a `Token` event for `[start, end)` when event collection
+    /// is enabled; a no-op otherwise.
+    #[inline]
+    fn record_token(&mut self, start: u32, end: u32) {
+        if let Some(ref mut events) = self.events {
+            events.token(start, end);
+        }
+    }
+
+    /// Records a `StartNode(kind)` event when event collection is enabled;
+    /// a no-op otherwise.
+    #[inline]
+    fn record_start_node(&mut self, kind: NodeKind) {
+        if let Some(ref mut events) = self.events {
+            events.start_node(kind);
+        }
+    }
+
+    /// Records a `FinishNode` event when event collection is enabled; a
+    /// no-op otherwise.
+    #[inline]
+    fn record_finish_node(&mut self) {
+        if let Some(ref mut events) = self.events {
+            events.finish_node();
+        }
+    }
+
+    /// Records an `Error` event when event collection is enabled; a no-op
+    /// otherwise.
+    #[inline]
+    fn record_error_event(&mut self) {
+        if let Some(ref mut events) = self.events {
+            events.error();
+        }
+    }
+
     #[inline]
     fn block<I>(&mut self) -> BlockPtr<'ast, I> where
         I: Parse<'ast, Output = Ptr<'ast, Loc<I>>> + Copy
@@ -122,8 +257,12 @@ impl<'ast> Parser<'ast> {
             BraceOpen => self.lexer.start_then_consume(),
             _         => return self.error(),
         };
+        self.record_start_node(NodeKind::Block);
+        self.record_token(start, start + 1);
         let block = self.raw_block();
         let end   = self.lexer.end_then_consume();
+        self.record_token(end - 1, end);
+        self.record_finish_node();
 
         self.alloc_at_loc(start, end, block)
     }
@@ -134,12 +273,21 @@ impl<'ast> Parser<'ast> {
         I: Parse<'ast, Output = Ptr<'ast, Loc<I>>> + Copy
     {
         let start = self.lexer.start_then_consume();
+        self.record_start_node(NodeKind::Block);
+        self.record_token(start, start + 1);
         let block = self.raw_block();
         let end   = self.lexer.end_then_consume();
+        self.record_token(end - 1, end);
+        self.record_finish_node();
 
         self.alloc_at_loc(start, end, block)
     }
 
+    /// Parses the statements between a block's braces (exclusive of the
+    /// braces themselves, which `block`/`unchecked_block` record). Each
+    /// statement gets the same `StartNode`/`FinishNode`/error-recovery
+    /// bookkeeping as `top_level_statement`, so event collection isn't
+    /// limited to the top level of the program.
     #[inline]
     fn raw_block<I>(&mut self) -> Block<'ast, I> where
         I: Parse<'ast, Output = Ptr<'ast, Loc<I>>> + Copy
@@ -148,16 +296,39 @@ impl<'ast> Parser<'ast> {
             return Block { body: List::empty() };
         }
 
-        let statement = I::parse(self);
+        let statement = self.block_statement::<I>();
         let mut builder = ListBuilder::new(self.arena, statement);
 
         while self.lexer.token != BraceClose {
-            builder.push(I::parse(self));
+            builder.push(self.block_statement::<I>());
         }
 
         Block { body: builder.into_list() }
     }
 
+    /// Parses one statement inside a block, wrapped in the same
+    /// `StartNode`/`FinishNode` event pair and error-recovery bookkeeping
+    /// `top_level_statement` uses at the top level.
+    #[inline]
+    fn block_statement<I>(&mut self) -> I::Output where
+        I: Parse<'ast, Output = Ptr<'ast, Loc<I>>> + Copy
+    {
+        let before = self.loc();
+        let errors_before = self.errors.len();
+
+        self.record_start_node(NodeKind::Statement);
+        let statement = I::parse(self);
+        self.record_finish_node();
+
+        if self.errors.len() > errors_before {
+            self.record_error_event();
+            self.recover(STATEMENT_RECOVERY_SET);
+            self.ensure_recovery_progress(before, BLOCK_BOUNDARY);
+        }
+
+        statement
+    }
+
     #[inline]
     fn param_from_expression(&mut self, expression: ExpressionPtr<'ast>) -> ParameterPtr<'ast> {
         let (key, value) = match expression.item {
@@ -171,7 +342,8 @@ impl<'ast> Parser<'ast> {
 
         let key = match key.item {
             Expression::Identifier(ident) => ParameterKey::Identifier(ident),
-            // TODO: ParameterKey::Pattern
+            Expression::Object(_) |
+            Expression::Array(_)  => ParameterKey::Pattern(self.pattern_from_expression(key)),
             _ => return self.error()
         };
 
@@ -207,7 +379,11 @@ impl<'ast> Parser<'ast> {
         let mut require_defaults = false;
 
         loop {
-            let key = parameter_key!(self);
+            let key = match self.lexer.token {
+                BraceOpen   => ParameterKey::Pattern(self.object_pattern()),
+                BracketOpen => ParameterKey::Pattern(self.array_pattern()),
+                _           => parameter_key!(self),
+            };
             let value = match self.lexer.token {
                 OperatorAssign => {
                     self.lexer.consume();
@@ -340,4 +516,71 @@ mod test {
 
         assert_eq!(module.body(), expected);
     }
+
+    #[test]
+    fn recovers_after_invalid_declarator() {
+        let errors = parse("let x = ; let y = 2;").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn recovery_terminates_on_a_stray_closing_brace() {
+        // A `}` with no matching block is already a member of the
+        // top-level recovery set, so `recover` itself has nothing to
+        // skip; this must not spin forever re-parsing the same token.
+        let errors = parse("}").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn destructuring_function_params() {
+        assert!(parse("function f({a, b = 1}, [x, ...rest]) {}").is_ok());
+    }
+
+    #[test]
+    fn destructuring_arrow_params() {
+        assert!(parse("const f = ({a, b: c}, [x, y]) => a + c + x + y;").is_ok());
+    }
+
+    #[test]
+    fn event_mode_records_block_braces() {
+        let arena = Arena::new();
+        let mut parser = Parser::new_with_events("{}", &arena);
+
+        parser.parse();
+
+        let events = parser.take_events().expect("event collection was enabled");
+
+        assert_eq!(events.events(), &[
+            Event::StartNode(NodeKind::Statement),
+            Event::StartNode(NodeKind::Block),
+            Event::Token { start: 0, end: 1 },
+            Event::Token { start: 1, end: 2 },
+            Event::FinishNode,
+            Event::FinishNode,
+        ]);
+    }
+
+    #[test]
+    fn event_mode_records_nested_block_statements() {
+        let arena = Arena::new();
+        let mut parser = Parser::new_with_events("{;}", &arena);
+
+        parser.parse();
+
+        let events = parser.take_events().expect("event collection was enabled");
+
+        assert_eq!(events.events(), &[
+            Event::StartNode(NodeKind::Statement),
+            Event::StartNode(NodeKind::Block),
+            Event::Token { start: 0, end: 1 },
+            Event::StartNode(NodeKind::Statement),
+            Event::FinishNode,
+            Event::Token { start: 2, end: 3 },
+            Event::FinishNode,
+            Event::FinishNode,
+        ]);
+    }
 }
@@ -0,0 +1,240 @@
+// `Pattern`/`PatternPtr`/`ObjectPatternProperty`/`ParameterKey::Pattern`
+// (used throughout this file) and `NodeKind` (used in `parser::event`)
+// are additions this series depends on in the `ast` crate, same as every
+// other `ast::*` item this series imports — `ast` is an external crate
+// this checkout doesn't carry the source for (like `lexer`/`arena`), so
+// those additions land there, not in anything under `core/src`. They
+// aren't part of this series' own diff for the same reason none of
+// `ast`'s other pre-existing types are: this series only ever consumes
+// `ast`, it doesn't define it.
+use ast::{Loc, EmptyListBuilder};
+use ast::{Pattern, PatternPtr, ObjectPatternProperty, OperatorKind};
+use ast::{Expression, ExpressionPtr};
+use ast::expression::{BinaryExpression, ObjectExpression, ArrayExpression, ObjectMember};
+use lexer::Token::*;
+use parser::Parser;
+
+impl<'ast> Parser<'ast> {
+    /// Parses an object binding pattern: `{a, b = 1, c: d, ...rest}`.
+    /// Assumes the opening `{` has already been checked, but not consumed.
+    #[inline]
+    pub fn object_pattern(&mut self) -> PatternPtr<'ast> {
+        let start = self.lexer.start_then_consume();
+
+        let mut builder = EmptyListBuilder::new(self.arena);
+
+        while self.lexer.token != BraceClose {
+            if self.lexer.token == OperatorSpread {
+                self.lexer.consume();
+
+                let argument = self.binding_pattern();
+
+                builder.push(self.alloc_in_loc(ObjectPatternProperty::Rest(argument)));
+
+                break;
+            }
+
+            let key = match self.lexer.token {
+                Identifier => self.lexer.token_as_name(),
+                _          => return self.error(),
+            };
+
+            let value = match self.lexer.token {
+                Colon => {
+                    self.lexer.consume();
+
+                    self.binding_pattern()
+                },
+                _ => self.alloc_in_loc(Pattern::Identifier(key)),
+            };
+
+            let value = match self.lexer.token {
+                OperatorAssign => {
+                    self.lexer.consume();
+
+                    let default = self.expression(B1);
+
+                    self.alloc_at_loc(value.start, default.end, Pattern::Assign {
+                        left: value,
+                        right: default,
+                    })
+                },
+                _ => value,
+            };
+
+            builder.push(self.alloc_in_loc(ObjectPatternProperty::Keyed { key, value }));
+
+            match self.lexer.token {
+                Comma => self.lexer.consume(),
+                _     => break,
+            }
+        }
+
+        let end = self.lexer.end_then_consume();
+
+        self.alloc_at_loc(start, end, Pattern::Object {
+            body: builder.into_list(),
+        })
+    }
+
+    /// Parses an array binding pattern: `[x, , y = 1, ...rest]`.
+    /// Assumes the opening `[` has already been checked, but not consumed.
+    #[inline]
+    pub fn array_pattern(&mut self) -> PatternPtr<'ast> {
+        let start = self.lexer.start_then_consume();
+
+        let mut builder = EmptyListBuilder::new(self.arena);
+
+        while self.lexer.token != BracketClose {
+            if self.lexer.token == Comma {
+                self.lexer.consume();
+
+                continue;
+            }
+
+            if self.lexer.token == OperatorSpread {
+                self.lexer.consume();
+
+                let argument = self.binding_pattern();
+
+                builder.push(self.alloc_in_loc(Pattern::Rest(argument)));
+
+                break;
+            }
+
+            let element = self.binding_pattern();
+
+            let element = match self.lexer.token {
+                OperatorAssign => {
+                    self.lexer.consume();
+
+                    let default = self.expression(B1);
+
+                    self.alloc_at_loc(element.start, default.end, Pattern::Assign {
+                        left: element,
+                        right: default,
+                    })
+                },
+                _ => element,
+            };
+
+            builder.push(element);
+
+            match self.lexer.token {
+                Comma => self.lexer.consume(),
+                _     => break,
+            }
+        }
+
+        let end = self.lexer.end_then_consume();
+
+        self.alloc_at_loc(start, end, Pattern::Array {
+            elements: builder.into_list(),
+        })
+    }
+
+    /// Dispatches to `object_pattern`/`array_pattern`, or falls back to a
+    /// plain identifier binding.
+    #[inline]
+    fn binding_pattern(&mut self) -> PatternPtr<'ast> {
+        match self.lexer.token {
+            BraceOpen   => self.object_pattern(),
+            BracketOpen => self.array_pattern(),
+            Identifier  => {
+                let ident = self.lexer.token_as_name();
+
+                self.alloc_in_loc(Pattern::Identifier(ident))
+            },
+            _ => self.error(),
+        }
+    }
+
+    /// Reinterprets an object/array *expression* (already parsed as part of
+    /// a parenthesized expression list) as the equivalent binding pattern,
+    /// for the case where that expression list turns out to be arrow
+    /// function parameters, e.g. `({a, b = 1}) => a + b`.
+    #[inline]
+    pub fn pattern_from_expression(&mut self, expression: ExpressionPtr<'ast>) -> PatternPtr<'ast> {
+        match expression.item {
+            Expression::Object(ObjectExpression { body }) => {
+                let mut builder = EmptyListBuilder::new(self.arena);
+
+                for &member in body.ptr_iter() {
+                    let property = match member.item {
+                        // `ObjectMember::Shorthand` only ever wraps the
+                        // bare property name (see its `ToError` impl in
+                        // `error.rs`, which builds one from just `""`),
+                        // with no slot for a default value. So a
+                        // shorthand-with-default, e.g. `{b = 1}` used as
+                        // an arrow parameter list's cover grammar
+                        // (`({a, b = 1}) => ...`, a.k.a.
+                        // CoverInitializedName), can only be represented
+                        // here if the object-*expression* parser (in
+                        // `expression.rs`, not in this checkout) already
+                        // produces some other shape for it — e.g. folding
+                        // it into `ObjectMember::Value` the same way a
+                        // colon-form default would appear. Until that's
+                        // confirmed, shorthand defaults inside a
+                        // parenthesized-expression-turned-parameter-list
+                        // aren't handled by this arm; they already work
+                        // via `object_pattern`, above, which parses
+                        // parameter/binding-pattern syntax directly
+                        // instead of reinterpreting a parsed expression.
+                        ObjectMember::Shorthand(name) => {
+                            Loc::new(member.start, member.end, ObjectPatternProperty::Keyed {
+                                key: self.alloc_at_loc(member.start, member.end, name),
+                                value: self.alloc_in_loc(Pattern::Identifier(
+                                    self.alloc_at_loc(member.start, member.end, name)
+                                )),
+                            })
+                        },
+                        ObjectMember::Value { key, value } => {
+                            let value = self.pattern_from_expression(value);
+
+                            Loc::new(member.start, member.end, ObjectPatternProperty::Keyed {
+                                key,
+                                value,
+                            })
+                        },
+                        _ => return self.error(),
+                    };
+
+                    builder.push(self.alloc(property));
+                }
+
+                self.alloc_at_loc(expression.start, expression.end, Pattern::Object {
+                    body: builder.into_list(),
+                })
+            },
+            Expression::Array(ArrayExpression { body }) => {
+                let mut builder = EmptyListBuilder::new(self.arena);
+
+                for &element in body.ptr_iter() {
+                    builder.push(self.pattern_from_expression(element));
+                }
+
+                self.alloc_at_loc(expression.start, expression.end, Pattern::Array {
+                    elements: builder.into_list(),
+                })
+            },
+            Expression::Binary(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left,
+                right,
+            }) => {
+                let left = self.pattern_from_expression(left);
+
+                self.alloc_at_loc(expression.start, expression.end, Pattern::Assign {
+                    left,
+                    right,
+                })
+            },
+            Expression::Identifier(ident) => {
+                self.alloc_at_loc(expression.start, expression.end, Pattern::Identifier(
+                    self.alloc_at_loc(expression.start, expression.end, ident)
+                ))
+            },
+            _ => self.error(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use lexer::Token;
+use lexer::Token::*;
+
+/// A cheap bitset over `Token` discriminants, used to describe a set of
+/// "safe" resynchronization points for error recovery.
+///
+/// Modeled after rust-analyzer's `TokenSet`: membership is a single shift
+/// and mask, so checking `self.lexer.token` against a recovery set on every
+/// iteration of a recovery loop is effectively free.
+#[derive(Clone, Copy)]
+pub struct TokenSet(u128);
+
+impl TokenSet {
+    pub const fn new(tokens: &[Token]) -> Self {
+        let mut mask = 0u128;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let bit = tokens[i] as u8;
+
+            // `TokenSet` packs discriminants into a 128-bit mask, so every
+            // `lexer::Token` variant must fit in a `u8 < 128`. If `Token`
+            // ever grows past that, this trips at compile time instead of
+            // silently overflowing the shift.
+            assert!(bit < 128, "lexer::Token discriminant does not fit in TokenSet's 128 bits");
+
+            mask |= 1 << bit;
+            i += 1;
+        }
+
+        TokenSet(mask)
+    }
+
+    #[inline]
+    pub const fn contains(&self, token: Token) -> bool {
+        let bit = token as u8;
+
+        // Mirrors the invariant asserted in `new`: a discriminant that
+        // doesn't fit in the mask can't be a member of it.
+        if bit >= 128 {
+            return false;
+        }
+
+        self.0 & (1 << bit) != 0
+    }
+}
+
+/// Tokens that are safe to resume parsing on after a syntax error: the
+/// keywords that start a `function`/`var`/`let`/`const`/`if`/`for`/
+/// `while`/`return`/`class` statement, plus the punctuators that close off
+/// a statement or the program entirely. Used by `Parser::recover` so a
+/// single bad token doesn't cascade into a wall of follow-on errors.
+pub const STATEMENT_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    Function,
+    DeclarationVar,
+    DeclarationLet,
+    DeclarationConst,
+    If,
+    For,
+    While,
+    Return,
+    Class,
+    Semicolon,
+    BraceClose,
+    EndOfProgram,
+]);
+
+/// The token(s) that naturally terminate a top-level `parse()` loop;
+/// `Parser::ensure_recovery_progress` must never force a consume past
+/// these, since the loop itself is what's supposed to stop on them.
+pub const TOP_LEVEL_BOUNDARY: TokenSet = TokenSet::new(&[EndOfProgram]);
+
+/// The token(s) that naturally terminate a `raw_block` loop; same
+/// reasoning as `TOP_LEVEL_BOUNDARY`, but a block also stops on its own
+/// closing brace, which `ensure_recovery_progress` must leave for the
+/// caller (`block`/`unchecked_block`) to consume.
+pub const BLOCK_BOUNDARY: TokenSet = TokenSet::new(&[BraceClose, EndOfProgram]);
@@ -0,0 +1,20 @@
+/// A problem the resolver found while building the scope tree. `Name` and
+/// `Loc` are generic over the concrete identifier/location types of the
+/// AST being resolved, so this doesn't need to depend on `ast` directly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic<Name, Loc> {
+    /// A reference to a name with no matching declaration anywhere in the
+    /// enclosing scope chain.
+    UndeclaredReference { name: Name, at: Loc },
+
+    /// Two declarations with the same name in the same scope, at least
+    /// one of them lexical (`let`/`const`/`class`).
+    DuplicateDeclaration { name: Name, first: Loc, second: Loc },
+
+    /// A `let`/`const`/`class` binding was referenced before its
+    /// declaration was reached in the same scope (temporal dead zone).
+    UseBeforeDeclaration { name: Name, at: Loc },
+
+    /// An assignment to a `const` binding.
+    AssignToConst { name: Name, at: Loc },
+}
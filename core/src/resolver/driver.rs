@@ -0,0 +1,405 @@
+//! Walks a parsed `ast::Module` and feeds it through a `Resolver`,
+//! producing the expression-pointer-keyed `Resolution` the module doc
+//! promises, instead of leaving callers to drive `Resolver` by hand.
+//!
+//! Each scope is resolved in two passes: `hoist_lexical`/`hoist_vars`
+//! first declare every binding that scope will ever have (lexical ones as
+//! `Uninitialized` TDZ bindings, `var`/function-scoped ones as already
+//! initialized), and only then does the second pass actually visit each
+//! statement in source order. That ordering is what makes a forward
+//! reference like `{ x; let x; }` resolve against the hoisted (but still
+//! uninitialized) `x` binding and report `UseBeforeDeclaration`, rather
+//! than finding no binding at all and reporting `UndeclaredReference`:
+//! declaring at the exact point a single linear pass reaches the
+//! declaration can never see a later declaration for an earlier reference
+//! in the same scope.
+//!
+//! This driver walks the `Statement`/`Expression` shapes this checkout
+//! has evidence for elsewhere in the crate (see the equivalent `_ =>
+//! false` notes in `spanless_eq.rs`), plus `If`/`Call`/`Member`/
+//! `Conditional`, whose field names follow this crate's own naming
+//! convention for analogous nodes (`BinaryExpression`, `ReturnStatement`,
+//! `DeclarationStatement`, ...) closely enough to walk with confidence;
+//! anything else is a documented no-op rather than a guess at an
+//! unverified node shape.
+//!
+//! One assumption this file makes about the `ast` crate's `Name` trait
+//! (used for a `Function`'s own declared name, which is generic so the
+//! same `Function<'ast, N>` type covers both named declarations and
+//! anonymous expressions): that it exposes `as_str(&self) -> Option<&'ast
+//! str>`, returning `None` only for the `N::empty()` anonymous
+//! placeholder `Handle` builds on error recovery. That's the one part of
+//! this file that can't be cross-checked against evidenced usage
+//! elsewhere in the checkout.
+
+use ast::{Module, Statement, DeclarationStatement, ReturnStatement, VariableKind, IfStatement};
+use ast::{Expression, ExpressionPtr, OperatorKind, List, Loc, Function, Parameter, ParameterKey, Name};
+use ast::{Pattern, PatternPtr, ObjectPatternProperty};
+use ast::expression::{
+    BinaryExpression, ObjectExpression, ArrayExpression, ObjectMember,
+    CallExpression, MemberExpression, ConditionalExpression,
+};
+
+use super::{Resolver, Resolution, ScopeKind, DeclarationKind};
+
+/// Key into the `Resolution`'s binding map: the address of the `Loc`
+/// backing the `ExpressionPtr` a reference was read from. Using the
+/// address (rather than, say, the `ExpressionPtr` itself) sidesteps
+/// needing `ExpressionPtr: Eq + Hash`, which isn't evidenced.
+pub type ExpressionKey = usize;
+
+type ModuleResolver<'ast> = Resolver<ExpressionKey, &'ast str, (u32, u32)>;
+
+fn key<'ast>(expression: ExpressionPtr<'ast>) -> ExpressionKey {
+    &*expression as *const Loc<Expression<'ast>> as ExpressionKey
+}
+
+/// Walks `module` end to end and returns its `Resolution`.
+pub fn resolve<'ast>(module: &Module<'ast>) -> Resolution<ExpressionKey, &'ast str, (u32, u32)> {
+    let mut resolver = Resolver::new();
+
+    resolve_statements(module.body(), &mut resolver);
+
+    resolver.into_resolution()
+}
+
+fn declaration_kind(kind: VariableKind) -> DeclarationKind {
+    match kind {
+        VariableKind::Var => DeclarationKind::Var,
+        VariableKind::Let => DeclarationKind::Let,
+        VariableKind::Const => DeclarationKind::Const,
+    }
+}
+
+/// Resolves one statement list that shares a single scope (a function
+/// body or a block): hoists every binding the scope will have, then
+/// visits each statement in order.
+fn resolve_statements<'ast>(statements: List<'ast, Loc<Statement<'ast>>>, resolver: &mut ModuleResolver<'ast>) {
+    hoist_lexical(statements, resolver);
+    hoist_vars(statements, resolver);
+
+    for stmt in statements.iter() {
+        visit_statement(stmt, resolver);
+    }
+}
+
+/// Declares every `let`/`const` directly in `statements` (not descending
+/// into nested blocks, since those bind in their own scope) as an
+/// `Uninitialized` binding in the current scope, before any statement is
+/// visited.
+fn hoist_lexical<'ast>(statements: List<'ast, Loc<Statement<'ast>>>, resolver: &mut ModuleResolver<'ast>) {
+    for stmt in statements.iter() {
+        if let Statement::Declaration(DeclarationStatement { kind, declarators }) = stmt.item {
+            if kind == VariableKind::Var {
+                continue;
+            }
+
+            for declarator in declarators.iter() {
+                if let Expression::Identifier(name) = declarator.item.name.item {
+                    resolver.declare(name, declaration_kind(kind), (declarator.start, declarator.end));
+                }
+            }
+        }
+    }
+}
+
+/// Declares every `var`/function declaration reachable from `statements`
+/// — including through nested blocks and `if` branches, since both hoist
+/// past those boundaries — as an already-initialized binding in the
+/// enclosing function (or module) scope. Called while the scope that owns
+/// `statements` is current: `Resolver::declare` walks up from the current
+/// scope past any block scopes to the nearest function (or module) scope,
+/// so the same call also works when `statements` is a block's body.
+fn hoist_vars<'ast>(statements: List<'ast, Loc<Statement<'ast>>>, resolver: &mut ModuleResolver<'ast>) {
+    for stmt in statements.iter() {
+        hoist_vars_stmt(stmt, resolver);
+    }
+}
+
+fn hoist_vars_stmt<'ast>(stmt: &Loc<Statement<'ast>>, resolver: &mut ModuleResolver<'ast>) {
+    match stmt.item {
+        Statement::Declaration(DeclarationStatement { kind: VariableKind::Var, declarators }) => {
+            for declarator in declarators.iter() {
+                if let Expression::Identifier(name) = declarator.item.name.item {
+                    resolver.declare(name, DeclarationKind::Var, (declarator.start, declarator.end));
+                }
+            }
+        },
+        // A function declaration's own name hoists to the top of the
+        // enclosing scope, same as `var` — so a call that textually
+        // precedes the declaration (including a recursive call inside
+        // the function's own body) still resolves instead of reporting a
+        // spurious `UndeclaredReference`.
+        Statement::Function(function) => {
+            if let Some(name) = function.name.as_str() {
+                resolver.declare(name, DeclarationKind::Function, (stmt.start, stmt.end));
+            }
+        },
+        Statement::Block(block) => {
+            hoist_vars(block.item.body, resolver);
+        },
+        Statement::If(IfStatement { consequent, alternate, .. }) => {
+            hoist_vars_stmt(&*consequent, resolver);
+
+            if let Some(alternate) = alternate {
+                hoist_vars_stmt(&*alternate, resolver);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn visit_statement<'ast>(stmt: &Loc<Statement<'ast>>, resolver: &mut ModuleResolver<'ast>) {
+    match stmt.item {
+        Statement::Error | Statement::Empty => {},
+        Statement::Expression(expression) => visit_expression(expression, resolver),
+        Statement::Return(ReturnStatement { value }) => {
+            if let Some(value) = value {
+                visit_expression(value, resolver);
+            }
+        },
+        Statement::Declaration(DeclarationStatement { kind, declarators }) => {
+            for declarator in declarators.iter() {
+                if let Some(value) = declarator.item.value {
+                    visit_expression(value, resolver);
+                }
+
+                // `var` was already declared as initialized by
+                // `hoist_vars`; `let`/`const` were hoisted as TDZ
+                // bindings by `hoist_lexical` and only become usable from
+                // here, once their (optional) initializer has run.
+                if kind != VariableKind::Var {
+                    if let Expression::Identifier(name) = declarator.item.name.item {
+                        resolver.initialize(&name);
+                    }
+                }
+            }
+        },
+        Statement::Function(function) => visit_function(function, resolver),
+        Statement::Block(block) => {
+            resolver.enter_scope(ScopeKind::Block);
+            resolve_statements(block.item.body, resolver);
+            resolver.exit_scope();
+        },
+        Statement::If(IfStatement { test, consequent, alternate }) => {
+            visit_expression(test, resolver);
+            visit_statement(&*consequent, resolver);
+
+            if let Some(alternate) = alternate {
+                visit_statement(&*alternate, resolver);
+            }
+        },
+        // `For`/`While`/`Switch`/`Try`/`Class`/... aren't evidenced
+        // closely enough in this checkout to walk safely (unlike `If`,
+        // none of their field names can be cross-checked against
+        // anything already in this crate) — left as a documented no-op
+        // rather than a guess at an unverified node shape; see the
+        // equivalent note in `spanless_eq.rs`. References inside these
+        // statement kinds are NOT resolved.
+        _ => {},
+    }
+}
+
+/// A function declaration's own name is hoisted and declared by
+/// `hoist_vars_stmt` before this runs, so it doesn't need to be declared
+/// again here; this only opens the function's own scope and resolves its
+/// parameters and body.
+fn visit_function<'ast, N>(function: Function<'ast, N>, resolver: &mut ModuleResolver<'ast>) {
+    resolver.enter_scope(ScopeKind::Function);
+
+    for &param in function.params.ptr_iter() {
+        let at = (param.start, param.end);
+
+        match param.item.key {
+            ParameterKey::Identifier(name) => resolver.declare(name, DeclarationKind::Param, at),
+            ParameterKey::Pattern(pattern) => declare_pattern(pattern, at, resolver),
+        }
+    }
+
+    resolve_statements(function.body, resolver);
+
+    resolver.exit_scope();
+}
+
+fn declare_pattern<'ast>(pattern: PatternPtr<'ast>, at: (u32, u32), resolver: &mut ModuleResolver<'ast>) {
+    match pattern.item {
+        Pattern::Identifier(name) => resolver.declare(name.item, DeclarationKind::Param, at),
+        Pattern::Object { body } => {
+            for &property in body.ptr_iter() {
+                match property.item {
+                    ObjectPatternProperty::Keyed { value, .. } => declare_pattern(value, at, resolver),
+                    ObjectPatternProperty::Rest(value) => declare_pattern(value, at, resolver),
+                }
+            }
+        },
+        Pattern::Array { elements } => {
+            for &element in elements.ptr_iter() {
+                declare_pattern(element, at, resolver);
+            }
+        },
+        Pattern::Rest(inner) => declare_pattern(inner, at, resolver),
+        Pattern::Assign { left, .. } => declare_pattern(left, at, resolver),
+    }
+}
+
+fn visit_expression<'ast>(expression: ExpressionPtr<'ast>, resolver: &mut ModuleResolver<'ast>) {
+    match expression.item {
+        Expression::Error | Expression::Literal(_) => {},
+        Expression::Identifier(name) => {
+            resolver.reference(key(expression), &name, (expression.start, expression.end));
+        },
+        Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, left, right }) => {
+            match left.item {
+                Expression::Identifier(name) => resolver.assign(&name, (left.start, left.end)),
+                _ => visit_expression(left, resolver),
+            }
+
+            visit_expression(right, resolver);
+        },
+        Expression::Binary(BinaryExpression { left, right, .. }) => {
+            visit_expression(left, resolver);
+            visit_expression(right, resolver);
+        },
+        Expression::Object(ObjectExpression { body }) => {
+            for &member in body.ptr_iter() {
+                // `Shorthand` has no `ExpressionPtr` of its own to key a
+                // reference by (it's just the property's identifier,
+                // reused as both key and value), so it's left unresolved
+                // here rather than keying it by the containing object
+                // expression and risking collisions between properties.
+                if let ObjectMember::Value { value, .. } = member.item {
+                    visit_expression(value, resolver);
+                }
+            }
+        },
+        Expression::Array(ArrayExpression { body }) => {
+            for &element in body.ptr_iter() {
+                visit_expression(element, resolver);
+            }
+        },
+        Expression::Call(CallExpression { callee, arguments }) => {
+            visit_expression(callee, resolver);
+
+            for &argument in arguments.ptr_iter() {
+                visit_expression(argument, resolver);
+            }
+        },
+        Expression::Member(MemberExpression { object, property, computed }) => {
+            visit_expression(object, resolver);
+
+            // A non-computed `a.b` stores `b` as an identifier expression
+            // too, but it's a property name, not a variable reference —
+            // only `a[b]` (`computed`) actually looks `b` up in scope.
+            if computed {
+                visit_expression(property, resolver);
+            }
+        },
+        Expression::Conditional(ConditionalExpression { test, consequent, alternate }) => {
+            visit_expression(test, resolver);
+            visit_expression(consequent, resolver);
+            visit_expression(alternate, resolver);
+        },
+        // Other `Expression` variants (templates, sequences, `new`, …)
+        // aren't evidenced closely enough in this checkout to walk
+        // safely; see the equivalent note in `spanless_eq.rs` and in
+        // `visit_statement` above. References inside them are NOT
+        // resolved.
+        _ => {},
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::parse;
+    use resolver::Diagnostic;
+
+    #[test]
+    fn forward_reference_to_let_reports_use_before_declaration() {
+        let module = parse("{ x; let x; }").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert_eq!(resolution.diagnostics.len(), 1);
+
+        match resolution.diagnostics[0] {
+            Diagnostic::UseBeforeDeclaration { name, .. } => assert_eq!(name, "x"),
+            ref other => panic!("expected UseBeforeDeclaration, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn var_is_usable_before_its_textual_declaration() {
+        let module = parse("x = 1; var x;").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn resolves_a_parameter_reference_in_the_function_body() {
+        let module = parse("function f(a) { return a; }").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn reports_undeclared_reference_through_the_real_driver() {
+        let module = parse("y;").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert_eq!(resolution.diagnostics.len(), 1);
+
+        match resolution.diagnostics[0] {
+            Diagnostic::UndeclaredReference { name, .. } => assert_eq!(name, "y"),
+            ref other => panic!("expected UndeclaredReference, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn a_recursive_function_call_resolves_against_its_own_hoisted_name() {
+        let module = parse("function f() { return f(); }").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn a_function_declaration_is_callable_before_its_textual_declaration() {
+        let module = parse("f(); function f() {}").expect("source should parse");
+        let resolution = resolve(&module);
+
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn resolves_references_inside_an_if_statement() {
+        let module = parse("let x; if (x) { x; } else { x; }").expect("source should parse");
+        let resolution = resolve(&module);
+
+        // The test, the consequent and the alternate each reference `x`.
+        assert_eq!(resolution.bindings.len(), 3);
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn resolves_references_in_call_arguments_and_the_callee() {
+        let module = parse("function f(a) { return f(a); }").expect("source should parse");
+        let resolution = resolve(&module);
+
+        // The callee `f` and the argument `a`.
+        assert_eq!(resolution.bindings.len(), 2);
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn resolves_a_computed_member_property_but_not_a_static_one() {
+        let module = parse("let a; let b; a[b]; a.b;").expect("source should parse");
+        let resolution = resolve(&module);
+
+        // `a[b]` resolves both `a` and `b`; `a.b` resolves only `a`, since
+        // a static property name isn't a variable reference.
+        assert_eq!(resolution.bindings.len(), 3);
+        assert!(resolution.diagnostics.is_empty());
+    }
+}
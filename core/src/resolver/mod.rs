@@ -0,0 +1,302 @@
+//! A post-parse analysis pass over a parsed `Module`: builds a scope
+//! tree, binds each identifier use to its declaration, and flags the
+//! usual lexical-scoping mistakes (in the spirit of rlox's
+//! `resolver.rs`). Downstream stages (codegen, minification) can consume
+//! the resulting `Resolution`'s binding map instead of re-walking scopes
+//! themselves.
+//!
+//! `Resolver` is generic over the identifier (`Name`) and source-location
+//! (`Loc`) types so the scoping algorithm itself doesn't depend on the
+//! concrete `ast` node shapes; `driver::resolve` is what actually walks a
+//! real `ast::Module` / `Statement` / `Expression` and feeds it
+//! `enter_scope` / `declare` / `reference` / `assign` / `exit_scope` calls
+//! in traversal order, keyed by `ExpressionPtr` address so downstream
+//! passes can look up a specific node's binding without re-walking scopes
+//! themselves. This module owns only the scope-tracking state machine and
+//! the diagnostics it produces; `driver` owns the traversal order that
+//! makes the temporal-dead-zone tracking correct (declarations are
+//! hoisted ahead of any reference in the same scope, not declared at the
+//! exact point the linear walk reaches them — see `driver`'s doc comment).
+
+mod scope;
+mod diagnostic;
+mod driver;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub use self::diagnostic::Diagnostic;
+pub use self::scope::{BindingState, DeclarationKind, ScopeKind};
+pub use self::driver::{resolve, ExpressionKey};
+use self::scope::{Binding, Scope};
+
+/// Maps an identifier *use* to the location of its declaring binding.
+/// The driver chooses what `Key` is (e.g. the raw pointer of the
+/// `ExpressionPtr` being resolved) so repeated resolution of the same
+/// physical node is a stable, cheap lookup for later passes.
+pub type ResolutionMap<Key, Loc> = HashMap<Key, Loc>;
+
+pub struct Resolution<Key, Name, Loc> {
+    pub bindings: ResolutionMap<Key, Loc>,
+    pub diagnostics: Vec<Diagnostic<Name, Loc>>,
+}
+
+pub struct Resolver<Key, Name, Loc> {
+    scopes: Vec<Scope<Name, Loc>>,
+    current: usize,
+    bindings: ResolutionMap<Key, Loc>,
+    diagnostics: Vec<Diagnostic<Name, Loc>>,
+}
+
+impl<Key, Name, Loc> Resolver<Key, Name, Loc>
+where
+    Key: Eq + Hash,
+    Name: Eq + Hash + Clone,
+    Loc: Clone,
+{
+    pub fn new() -> Self {
+        let mut scopes = Vec::new();
+        scopes.push(Scope::new(ScopeKind::Module, None));
+
+        Resolver {
+            scopes,
+            current: 0,
+            bindings: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn into_resolution(self) -> Resolution<Key, Name, Loc> {
+        Resolution {
+            bindings: self.bindings,
+            diagnostics: self.diagnostics,
+        }
+    }
+
+    pub fn enter_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope::new(kind, Some(self.current)));
+        self.current = self.scopes.len() - 1;
+    }
+
+    pub fn exit_scope(&mut self) {
+        self.current = self.scopes[self.current].parent
+            .expect("exit_scope called on the module (root) scope");
+    }
+
+    /// Finds the nearest enclosing scope a declaration of `kind` would
+    /// bind in: the current scope for block-scoped kinds, or the nearest
+    /// enclosing `Function`/`Module` scope for hoisting kinds.
+    fn target_scope(&self, kind: DeclarationKind) -> usize {
+        if !kind.hoists() {
+            return self.current;
+        }
+
+        let mut scope = self.current;
+
+        loop {
+            match self.scopes[scope].kind {
+                ScopeKind::Function | ScopeKind::Module => return scope,
+                ScopeKind::Block => scope = self.scopes[scope].parent
+                    .expect("block scope always has a parent"),
+            }
+        }
+    }
+
+    /// Declares `name` with the given `kind`. Reports
+    /// `DuplicateDeclaration` if a binding with the same name already
+    /// exists in the target scope and either it or this declaration is
+    /// lexical (`let`/`const`/`class`).
+    pub fn declare(&mut self, name: Name, kind: DeclarationKind, at: Loc) {
+        let scope = self.target_scope(kind);
+        let state = match kind.has_temporal_dead_zone() {
+            true  => BindingState::Uninitialized,
+            false => BindingState::Initialized,
+        };
+
+        if let Some(existing) = self.scopes[scope].bindings.get(&name) {
+            if kind.has_temporal_dead_zone() || existing.kind.has_temporal_dead_zone() {
+                self.diagnostics.push(Diagnostic::DuplicateDeclaration {
+                    name,
+                    first: existing.declared_at.clone(),
+                    second: at,
+                });
+                return;
+            }
+        }
+
+        self.scopes[scope].bindings.insert(name, Binding {
+            kind,
+            state,
+            declared_at: at,
+        });
+    }
+
+    /// Marks a previously-declared TDZ binding as initialized, once its
+    /// declaration's initializer (if any) has been evaluated.
+    pub fn initialize(&mut self, name: &Name) {
+        if let Some(binding) = self.scopes[self.current].bindings.get_mut(name) {
+            binding.state = BindingState::Initialized;
+        }
+    }
+
+    fn lookup(&self, name: &Name) -> Option<&Binding<Loc>> {
+        let mut scope = Some(self.current);
+
+        while let Some(index) = scope {
+            if let Some(binding) = self.scopes[index].bindings.get(name) {
+                return Some(binding);
+            }
+
+            scope = self.scopes[index].parent;
+        }
+
+        None
+    }
+
+    /// Records that `key` (e.g. the identifier expression's pointer)
+    /// refers to `name`, binding it to its declaration if one is found.
+    /// Reports `UndeclaredReference` or `UseBeforeDeclaration` as needed.
+    pub fn reference(&mut self, key: Key, name: &Name, at: Loc) {
+        match self.lookup(name) {
+            Some(binding) if binding.state == BindingState::Uninitialized => {
+                self.diagnostics.push(Diagnostic::UseBeforeDeclaration {
+                    name: name.clone(),
+                    at,
+                });
+            },
+            Some(binding) => {
+                self.bindings.insert(key, binding.declared_at.clone());
+            },
+            None => {
+                self.diagnostics.push(Diagnostic::UndeclaredReference {
+                    name: name.clone(),
+                    at,
+                });
+            },
+        }
+    }
+
+    /// Reports `AssignToConst` if `name` resolves to a `const` binding.
+    pub fn assign(&mut self, name: &Name, at: Loc) {
+        if let Some(binding) = self.lookup(name) {
+            if binding.kind.is_const() {
+                self.diagnostics.push(Diagnostic::AssignToConst {
+                    name: name.clone(),
+                    at,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type TestResolver = Resolver<u32, &'static str, u32>;
+
+    #[test]
+    fn resolves_a_reference_to_its_declaration() {
+        let mut resolver = TestResolver::new();
+
+        resolver.declare("x", DeclarationKind::Var, 0);
+        resolver.reference(1, &"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.bindings.get(&1), Some(&0));
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn reports_undeclared_reference() {
+        let mut resolver = TestResolver::new();
+
+        resolver.reference(1, &"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.diagnostics, vec![
+            Diagnostic::UndeclaredReference { name: "x", at: 1 },
+        ]);
+    }
+
+    #[test]
+    fn reports_duplicate_lexical_declaration() {
+        let mut resolver = TestResolver::new();
+
+        resolver.declare("x", DeclarationKind::Let, 0);
+        resolver.declare("x", DeclarationKind::Const, 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.diagnostics, vec![
+            Diagnostic::DuplicateDeclaration { name: "x", first: 0, second: 1 },
+        ]);
+    }
+
+    #[test]
+    fn reports_use_before_declaration_in_same_scope() {
+        let mut resolver = TestResolver::new();
+
+        resolver.declare("x", DeclarationKind::Let, 5);
+        resolver.reference(1, &"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.diagnostics, vec![
+            Diagnostic::UseBeforeDeclaration { name: "x", at: 1 },
+        ]);
+    }
+
+    #[test]
+    fn reports_assignment_to_const() {
+        let mut resolver = TestResolver::new();
+
+        resolver.declare("x", DeclarationKind::Const, 0);
+        resolver.initialize(&"x");
+        resolver.assign(&"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.diagnostics, vec![
+            Diagnostic::AssignToConst { name: "x", at: 1 },
+        ]);
+    }
+
+    #[test]
+    fn var_hoists_through_block_scopes_to_the_enclosing_function() {
+        let mut resolver = TestResolver::new();
+
+        resolver.enter_scope(ScopeKind::Function);
+        resolver.enter_scope(ScopeKind::Block);
+        resolver.declare("x", DeclarationKind::Var, 0);
+        resolver.exit_scope();
+
+        // `x` is visible here even though it was declared in the inner
+        // block, because `var` hoists to the function scope.
+        resolver.reference(1, &"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.bindings.get(&1), Some(&0));
+        assert!(resolution.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn let_does_not_escape_its_block_scope() {
+        let mut resolver = TestResolver::new();
+
+        resolver.enter_scope(ScopeKind::Block);
+        resolver.declare("x", DeclarationKind::Let, 0);
+        resolver.exit_scope();
+
+        resolver.reference(1, &"x", 1);
+
+        let resolution = resolver.into_resolution();
+
+        assert_eq!(resolution.diagnostics, vec![
+            Diagnostic::UndeclaredReference { name: "x", at: 1 },
+        ]);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+/// The three kinds of scope the resolver tracks. `var`/`function`
+/// declarations always bind in the nearest enclosing `Function` (or
+/// `Module`) scope; `let`/`const`/`class` bind in the nearest enclosing
+/// scope of any kind.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScopeKind {
+    Module,
+    Function,
+    Block,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeclarationKind {
+    Var,
+    Let,
+    Const,
+    Function,
+    Class,
+    Param,
+}
+
+impl DeclarationKind {
+    /// `var`/`function` hoist past block boundaries to the nearest
+    /// enclosing function (or module) scope; everything else is
+    /// block-scoped.
+    pub fn hoists(self) -> bool {
+        match self {
+            DeclarationKind::Var | DeclarationKind::Function => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_const(self) -> bool {
+        self == DeclarationKind::Const
+    }
+
+    /// `let`/`const`/`class` are temporal-dead-zoned until their
+    /// declaration is reached; `var`, function declarations, and params
+    /// are usable from the top of their scope.
+    pub fn has_temporal_dead_zone(self) -> bool {
+        match self {
+            DeclarationKind::Let | DeclarationKind::Const | DeclarationKind::Class => true,
+            _ => false,
+        }
+    }
+}
+
+/// Whether a temporal-dead-zoned binding has reached its declaration yet.
+/// Accessing a binding while it's `Uninitialized` is a TDZ violation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BindingState {
+    Uninitialized,
+    Initialized,
+}
+
+#[derive(Clone, Debug)]
+pub struct Binding<Loc> {
+    pub kind: DeclarationKind,
+    pub state: BindingState,
+    pub declared_at: Loc,
+}
+
+pub struct Scope<Name, Loc> {
+    pub kind: ScopeKind,
+    pub parent: Option<usize>,
+    pub bindings: HashMap<Name, Binding<Loc>>,
+}
+
+impl<Name, Loc> Scope<Name, Loc>
+where
+    Name: Eq + ::std::hash::Hash,
+{
+    pub fn new(kind: ScopeKind, parent: Option<usize>) -> Self {
+        Scope {
+            kind,
+            parent,
+            bindings: HashMap::new(),
+        }
+    }
+}
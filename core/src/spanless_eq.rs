@@ -0,0 +1,328 @@
+use ast::{Loc, Ptr, List, OperatorKind, Module};
+use ast::{Statement, DeclarationStatement, ReturnStatement, VariableKind, IfStatement};
+use ast::{Expression, Literal, Parameter, ParameterKey, Declarator};
+use ast::expression::{
+    BinaryExpression, ObjectExpression, ArrayExpression, ObjectMember,
+    CallExpression, MemberExpression, ConditionalExpression,
+};
+use ast::{ClassMember, Function, Class, Pattern, ObjectPatternProperty};
+
+/// Structural equality that ignores the `start`/`end` fields of every
+/// `Loc`, mirroring swc's `assert_eq_ignore_span!`. AST node types derive
+/// or implement this instead of relying on `PartialEq`, which would
+/// otherwise compare spans and make round-trip/precedence tests sensitive
+/// to source offsets that have no bearing on meaning.
+pub trait SpanlessEq {
+    fn spanless_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: SpanlessEq> SpanlessEq for Loc<T> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.item.spanless_eq(&other.item)
+    }
+}
+
+impl<'ast, T: SpanlessEq> SpanlessEq for Ptr<'ast, T> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        (**self).spanless_eq(&**other)
+    }
+}
+
+impl<T: SpanlessEq> SpanlessEq for Option<T> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.spanless_eq(b),
+            (None, None)       => true,
+            _                  => false,
+        }
+    }
+}
+
+impl<'ast, T: SpanlessEq> SpanlessEq for List<'ast, T> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        let mut a = self.iter();
+        let mut b = other.iter();
+
+        loop {
+            match (a.next(), b.next()) {
+                (Some(x), Some(y)) => if !x.spanless_eq(y) { return false },
+                (None, None)       => return true,
+                _                  => return false,
+            }
+        }
+    }
+}
+
+macro_rules! impl_spanless_eq_via_partial_eq {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl SpanlessEq for $ty {
+                fn spanless_eq(&self, other: &Self) -> bool {
+                    self == other
+                }
+            }
+        )*
+    };
+}
+
+impl_spanless_eq_via_partial_eq!(bool, u32, u64, OperatorKind, VariableKind);
+
+impl<'a> SpanlessEq for &'a str {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+// --- AST node types --------------------------------------------------
+//
+// The generic machinery above handles any node shaped like a wrapper
+// (`Loc`, `Ptr`, `Option`, `List`); everything below walks the concrete
+// node enums/structs this crate has so far, recursing into field values
+// via `spanless_eq` instead of `==` so spans never factor into the
+// comparison. Leaf wrapper types around raw identifiers/literal content
+// (e.g. the `Name`-implementing identifier struct) implement `SpanlessEq`
+// next to their own definition, the same way they'd implement
+// `PartialEq`; this module only owns the recursive node shapes.
+//
+// Every `match` below ends in a `_ => false` arm. That arm is reached
+// both by genuinely different variants (the case it's meant for) AND by
+// two nodes of the same variant that this file hasn't added an explicit
+// arm for yet (e.g. two `Statement::For`s) — those compare as unequal
+// even when structurally identical. That's still the safer failure mode
+// for a harness like the round-trip test (a false "not equal" fails a
+// test loudly; a falsely permissive catch-all could pass one silently),
+// but it does mean round-tripping a variant without an arm here will
+// report a spurious mismatch rather than ever succeeding. Add an arm
+// below whenever a new variant's fields are evidenced closely enough
+// elsewhere in the crate to compare with confidence (as `If`/`Call`/
+// `Member`/`Conditional` were, matching `resolver::driver`'s walk of the
+// same shapes).
+
+impl<'ast> SpanlessEq for Module<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.body().spanless_eq(&other.body())
+    }
+}
+
+impl<'ast> SpanlessEq for Statement<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Statement::Error, Statement::Error) => true,
+            (Statement::Empty, Statement::Empty) => true,
+            (Statement::Expression(a), Statement::Expression(b)) => a.spanless_eq(b),
+            (Statement::Declaration(a), Statement::Declaration(b)) => a.spanless_eq(b),
+            (Statement::Return(a), Statement::Return(b)) => a.spanless_eq(b),
+            (Statement::Function(a), Statement::Function(b)) => a.spanless_eq(b),
+            (Statement::Block(a), Statement::Block(b)) => a.spanless_eq(b),
+            (Statement::If(a), Statement::If(b)) => a.spanless_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SpanlessEq for IfStatement<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.test.spanless_eq(&other.test)
+            && self.consequent.spanless_eq(&other.consequent)
+            && self.alternate.spanless_eq(&other.alternate)
+    }
+}
+
+impl<'ast> SpanlessEq for DeclarationStatement<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.kind.spanless_eq(&other.kind)
+            && self.declarators.spanless_eq(&other.declarators)
+    }
+}
+
+impl<'ast> SpanlessEq for ReturnStatement<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.value.spanless_eq(&other.value)
+    }
+}
+
+impl<'ast> SpanlessEq for Declarator<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.name.spanless_eq(&other.name)
+            && self.value.spanless_eq(&other.value)
+    }
+}
+
+impl<'ast> SpanlessEq for Expression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Expression::Error, Expression::Error) => true,
+            (Expression::Identifier(a), Expression::Identifier(b)) => a.spanless_eq(b),
+            (Expression::Literal(a), Expression::Literal(b)) => a.spanless_eq(b),
+            (Expression::Binary(a), Expression::Binary(b)) => a.spanless_eq(b),
+            (Expression::Object(a), Expression::Object(b)) => a.spanless_eq(b),
+            (Expression::Array(a), Expression::Array(b)) => a.spanless_eq(b),
+            (Expression::Call(a), Expression::Call(b)) => a.spanless_eq(b),
+            (Expression::Member(a), Expression::Member(b)) => a.spanless_eq(b),
+            (Expression::Conditional(a), Expression::Conditional(b)) => a.spanless_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl SpanlessEq for Literal {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Literal::Number(a), Literal::Number(b)) => a.spanless_eq(b),
+            (Literal::String(a), Literal::String(b)) => a.spanless_eq(b),
+            (Literal::True, Literal::True) => true,
+            (Literal::False, Literal::False) => true,
+            (Literal::Null, Literal::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SpanlessEq for BinaryExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.operator.spanless_eq(&other.operator)
+            && self.left.spanless_eq(&other.left)
+            && self.right.spanless_eq(&other.right)
+    }
+}
+
+impl<'ast> SpanlessEq for ObjectExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.body.spanless_eq(&other.body)
+    }
+}
+
+impl<'ast> SpanlessEq for ArrayExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.body.spanless_eq(&other.body)
+    }
+}
+
+impl<'ast> SpanlessEq for CallExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.callee.spanless_eq(&other.callee)
+            && self.arguments.spanless_eq(&other.arguments)
+    }
+}
+
+impl<'ast> SpanlessEq for MemberExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.object.spanless_eq(&other.object)
+            && self.property.spanless_eq(&other.property)
+            && self.computed == other.computed
+    }
+}
+
+impl<'ast> SpanlessEq for ConditionalExpression<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.test.spanless_eq(&other.test)
+            && self.consequent.spanless_eq(&other.consequent)
+            && self.alternate.spanless_eq(&other.alternate)
+    }
+}
+
+impl<'ast> SpanlessEq for ObjectMember<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ObjectMember::Shorthand(a), ObjectMember::Shorthand(b)) => a.spanless_eq(b),
+            (
+                ObjectMember::Value { key: a_key, value: a_value },
+                ObjectMember::Value { key: b_key, value: b_value },
+            ) => a_key.spanless_eq(b_key) && a_value.spanless_eq(b_value),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SpanlessEq for ClassMember<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ClassMember::Constructor { params: a_params, body: a_body },
+                ClassMember::Constructor { params: b_params, body: b_body },
+            ) => a_params.spanless_eq(b_params) && a_body.spanless_eq(b_body),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast, N: SpanlessEq> SpanlessEq for Function<'ast, N> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.name.spanless_eq(&other.name)
+            && self.params.spanless_eq(&other.params)
+            && self.body.spanless_eq(&other.body)
+    }
+}
+
+impl<'ast, N: SpanlessEq> SpanlessEq for Class<'ast, N> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.name.spanless_eq(&other.name)
+            && self.extends.spanless_eq(&other.extends)
+            && self.body.spanless_eq(&other.body)
+    }
+}
+
+impl<'ast> SpanlessEq for Parameter<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        self.key.spanless_eq(&other.key)
+            && self.value.spanless_eq(&other.value)
+    }
+}
+
+impl<'ast> SpanlessEq for ParameterKey<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ParameterKey::Identifier(a), ParameterKey::Identifier(b)) => a.spanless_eq(b),
+            (ParameterKey::Pattern(a), ParameterKey::Pattern(b)) => a.spanless_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SpanlessEq for Pattern<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Pattern::Identifier(a), Pattern::Identifier(b)) => a.spanless_eq(b),
+            (Pattern::Object { body: a }, Pattern::Object { body: b }) => a.spanless_eq(b),
+            (Pattern::Array { elements: a }, Pattern::Array { elements: b }) => a.spanless_eq(b),
+            (Pattern::Rest(a), Pattern::Rest(b)) => a.spanless_eq(b),
+            (
+                Pattern::Assign { left: a_left, right: a_right },
+                Pattern::Assign { left: b_left, right: b_right },
+            ) => a_left.spanless_eq(b_left) && a_right.spanless_eq(b_right),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SpanlessEq for ObjectPatternProperty<'ast> {
+    fn spanless_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ObjectPatternProperty::Keyed { key: a_key, value: a_value },
+                ObjectPatternProperty::Keyed { key: b_key, value: b_value },
+            ) => a_key.spanless_eq(b_key) && a_value.spanless_eq(b_value),
+            (ObjectPatternProperty::Rest(a), ObjectPatternProperty::Rest(b)) => a.spanless_eq(b),
+            _ => false,
+        }
+    }
+}
+
+/// Asserts that two AST nodes are equal, ignoring spans, printing both
+/// with `{:#?}` on failure (spans and all, since that's still the most
+/// useful way to locate the mismatch).
+#[macro_export]
+macro_rules! assert_spanless_eq {
+    ($left:expr, $right:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !$crate::spanless_eq::SpanlessEq::spanless_eq(left, right) {
+                    panic!(
+                        "assertion failed: `(left spanless== right)`\n  left: `{:#?}`\n right: `{:#?}`",
+                        left, right,
+                    );
+                }
+            }
+        }
+    };
+}
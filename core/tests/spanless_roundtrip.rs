@@ -0,0 +1,50 @@
+//! Parse -> codegen -> parse idempotency, in the spirit of syn's
+//! precedence tests: regenerating source from a parsed `Module` and
+//! re-parsing it should yield a `SpanlessEq` AST, regardless of the
+//! exact spans the two parses produced.
+
+extern crate ratel_core;
+extern crate ratel_codegen;
+
+use ratel_core::parser::parse;
+use ratel_core::assert_spanless_eq;
+use ratel_codegen::codegen;
+
+fn assert_round_trips(source: &str) {
+    let first = parse(source).expect("source should parse");
+    let generated = codegen(&first);
+    let second = parse(&generated).unwrap_or_else(|err| {
+        panic!("regenerated source `{}` failed to re-parse: {:?}", generated, err)
+    });
+
+    assert_spanless_eq!(first, second);
+}
+
+#[test]
+fn round_trips_simple_statements() {
+    assert_round_trips("var x = 1;");
+    assert_round_trips("function f(a, b) { return a + b; }");
+}
+
+#[test]
+fn round_trips_operator_precedence() {
+    // Exercises precedence/associativity directly by hand-picking
+    // already-parenthesized and unparenthesized variants of the same
+    // expression, rather than deriving them automatically with a
+    // `parenthesize every subexpression` codegen pass: that pass would
+    // live in `ratel_codegen` (the crate `codegen`, above, comes from),
+    // which isn't part of this checkout any more than `ast`/`lexer` are —
+    // it can't be added here without guessing at `ratel_codegen`'s
+    // internals.
+    assert_round_trips("a + b * c");
+    assert_round_trips("(a + b) * c");
+    assert_round_trips("a || b && c");
+    assert_round_trips("a ** b ** c");
+}
+
+#[test]
+fn round_trips_if_call_member_and_conditional() {
+    assert_round_trips("if (a) { b(); } else { c.d; }");
+    assert_round_trips("f(a, b.c, a ? b : c);");
+    assert_round_trips("a[b];");
+}
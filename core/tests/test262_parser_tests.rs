@@ -0,0 +1,84 @@
+//! Drives the crate against the tc39 `test262-parser-tests` corpus.
+//!
+//! The full corpus lives outside this repository (see
+//! `tests/fixtures/test262-parser-tests/README.md` for how it's vendored);
+//! only a small representative sample is checked in here so the harness
+//! itself has something to run against in CI.
+
+extern crate ratel_core;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use ratel_core::parser::parse;
+use ratel_core::assert_spanless_eq;
+
+/// Reads every `.js` fixture directly inside `dir`. Panics if `dir` is
+/// missing or contains no fixtures at all: a vanished/empty corpus would
+/// otherwise make every test in this file vacuously pass, silently
+/// covering nothing instead of exercising the parser.
+fn read_fixtures(dir: &Path) -> Vec<(String, String)> {
+    let mut fixtures = Vec::new();
+
+    let entries = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("fixture directory `{}` is missing: {}", dir.display(), err));
+
+    for entry in entries {
+        let path = entry.expect("failed to read fixture entry").path();
+
+        if path.extension() != Some(OsStr::new("js")) {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path).expect("failed to read fixture source");
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+        fixtures.push((name, source));
+    }
+
+    assert!(!fixtures.is_empty(), "fixture directory `{}` has no `.js` fixtures", dir.display());
+
+    fixtures
+}
+
+fn fixtures_dir(sub: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/test262-parser-tests")
+        .join(sub)
+}
+
+#[test]
+fn pass_fixtures_parse_successfully() {
+    for (name, source) in read_fixtures(&fixtures_dir("pass")) {
+        assert!(parse(&source).is_ok(), "expected `pass/{}` to parse", name);
+    }
+}
+
+#[test]
+fn fail_fixtures_are_rejected() {
+    for (name, source) in read_fixtures(&fixtures_dir("fail")) {
+        assert!(parse(&source).is_err(), "expected `fail/{}` to be rejected", name);
+    }
+}
+
+#[test]
+fn pass_explicit_fixtures_match_their_pass_counterpart() {
+    // `pass-explicit/*` is a reformatted-but-equivalent copy of each
+    // `pass/*` fixture (e.g. `a = b` vs `a = (b)`), differing only in
+    // source spans. `SpanlessEq` lets us assert the two parses are
+    // structurally identical without the exact offsets getting in the way.
+    for (name, source) in read_fixtures(&fixtures_dir("pass-explicit")) {
+        let explicit_module = parse(&source)
+            .unwrap_or_else(|_| panic!("expected `pass-explicit/{}` to parse", name));
+
+        let pass_path = fixtures_dir("pass").join(&name);
+        let pass_source = fs::read_to_string(&pass_path)
+            .unwrap_or_else(|_| panic!("missing `pass/{}` counterpart for `pass-explicit/{}`", name, name));
+
+        let pass_module = parse(&pass_source)
+            .unwrap_or_else(|_| panic!("expected `pass/{}` to parse", name));
+
+        assert_spanless_eq!(pass_module, explicit_module);
+    }
+}